@@ -1,11 +1,32 @@
 use crate::geom::*;
+use crate::planner::{Command, Planner};
+use crate::relations::{Relationship, Relationships};
+use crate::ring_buffer::RingBuffer;
 use common::models::{GameState, PlayerState, BulletState, BULLET_SPEED};
 use std::{collections::HashMap, time::{Instant, Duration}};
 
+/// Radius, in world units, within which a bullet counts as hitting a player.
+const DEFAULT_COLLISION_RADIUS: f32 = 18.0;
+
+/// Per-tick time budget for the planner, kept comfortably under the
+/// server's tick rate so a slow search never makes the bot fall behind.
+const DECISION_BUDGET: Duration = Duration::from_millis(40);
+
+const PLANNER_SEED: u64 = 0xC0FFEE;
+
+// How much a unit of distance counts against a hostile's projected score
+// when ranking targets, so `most_valuable_hostile` doesn't chase a juicy
+// score clear across the map while a weaker target is in our face.
+const TARGET_DISTANCE_PENALTY: f32 = 0.05;
+
 pub struct Analyzer {
     own_player_id: u32,
     players: HashMap<u32, Player>,
     bullets: Vec<Bullet>,
+    collision_radius: f32,
+    planner: Planner,
+    relationships: Relationships,
+    last_command: Command,
 }
 
 impl Analyzer {
@@ -14,9 +35,35 @@ impl Analyzer {
             own_player_id: 0,
             players: HashMap::new(),
             bullets: Vec::new(),
+            collision_radius: DEFAULT_COLLISION_RADIUS,
+            planner: Planner::new(PLANNER_SEED),
+            relationships: Relationships::new(Relationship::Hostile),
+            last_command: Command { angle: 0.0, thrust: 0.0 },
         }
     }
 
+    // Merges a relationship override table, e.g. from a team/config file,
+    // into the existing overrides. Players not listed keep any relationship
+    // set earlier (e.g. by `set_own_player_id`); anyone never set at all is
+    // treated as hostile, the default set up in `new()`.
+    pub fn load_relationships(&mut self, config: HashMap<u32, Relationship>) {
+        for (player_id, relationship) in config {
+            self.relationships.set(player_id, relationship);
+        }
+    }
+
+    pub fn set_relationship(&mut self, player_id: u32, relationship: Relationship) {
+        self.relationships.set(player_id, relationship);
+    }
+
+    pub fn set_collision_radius(&mut self, radius: f32) {
+        self.collision_radius = radius;
+    }
+
+    pub fn collision_radius(&self) -> f32 {
+        self.collision_radius
+    }
+
     pub fn push_state(&mut self, state: &GameState, time: Instant) {
         let mut players = HashMap::new();
         for player_state in state.players.iter() {
@@ -35,6 +82,18 @@ impl Analyzer {
             .iter()
             .map(|state| Bullet::new(&state))
             .collect();
+
+        // Re-plan against the state we just ingested, so each tick's command
+        // reflects the latest positions. Skipped until we know who we are,
+        // since `decide()` reads `own_player()`.
+        if self.players.contains_key(&self.own_player_id) {
+            self.last_command = self.decide();
+        }
+    }
+
+    // The command `decide()` produced for the most recently pushed state.
+    pub fn command(&self) -> Command {
+        self.last_command
     }
 
     pub fn player<'a>(&'a self, id: u32) -> &'a Player {
@@ -43,22 +102,161 @@ impl Analyzer {
 
     pub fn set_own_player_id(&mut self, id: u32) {
         self.own_player_id = id;
+        self.relationships.set(id, Relationship::SelfPlayer);
     }
 
     pub fn own_player<'a>(&'a self) -> &'a Player {
         self.player(self.own_player_id)
     }
 
+    // Aims at where `target` will be by the time a bullet fired now could
+    // reach it, rather than where it is standing right now. Falls back to
+    // direct aim if the target is outrunning the bullet.
     pub fn angle_to(&self, target: u32) -> Radian {
-        self.own_player()
-            .position
-            .angle_to(&self.player(target).position)
+        let shooter = self.own_player().position;
+        let target_player = self.player(target);
+        let target_velocity = target_player.trajectory.last_velocity();
+
+        let aim_point = intercept_point(&shooter, &target_player.position, &target_velocity, BULLET_SPEED)
+            .unwrap_or(target_player.position);
+        shooter.angle_to(&aim_point)
     }
 
-    pub fn bullets_to_collide(&self, _until: Instant) -> Vec<Bullet> {
-        // TODO
-        unimplemented!();
+    fn hostiles<'a>(&'a self) -> impl Iterator<Item = &'a Player> {
+        self.players
+            .values()
+            .filter(move |player| self.relationships.is_hostile(player.id))
     }
+
+    // The closest hostile player, ignoring neutrals and self. Useful for a
+    // simple "dodge/engage whoever's nearest" policy.
+    pub fn nearest_hostile<'a>(&'a self) -> Option<&'a Player> {
+        let own_position = self.own_player().position;
+        self.hostiles().min_by(|a, b| {
+            own_position
+                .distance(&a.position)
+                .partial_cmp(&own_position.distance(&b.position))
+                .unwrap()
+        })
+    }
+
+    // The hostile player worth engaging most: a high projected score at
+    // close range beats a high score far away.
+    pub fn most_valuable_hostile<'a>(&'a self, after: Duration) -> Option<&'a Player> {
+        let own_position = self.own_player().position;
+        self.hostiles().max_by(|a, b| {
+            target_value(&own_position, a, after)
+                .partial_cmp(&target_value(&own_position, b, after))
+                .unwrap()
+        })
+    }
+
+    // Drives one budgeted planning pass for the current state and returns
+    // the command to apply this tick. Called once per `push_state`, so the
+    // planner's search time never outruns the server's tick rate.
+    //
+    // The planner is swapped out for the duration of the call so its `&mut
+    // self` (needed to advance its RNG stream) doesn't alias the `&Analyzer`
+    // it reads from.
+    pub fn decide(&mut self) -> Command {
+        let mut planner = std::mem::replace(&mut self.planner, Planner::new(PLANNER_SEED));
+        let command = planner.plan(self, DECISION_BUDGET);
+        self.planner = planner;
+        command
+    }
+
+    // Finds the bullets that will pass within `collision_radius` of
+    // `own_player()` before `until`, ordered by soonest impact first.
+    //
+    // For each bullet we work in the reference frame of the player (i.e.
+    // subtract the player's own velocity) and find the time of closest
+    // approach of the bullet's straight-line path to the player, clamped to
+    // the `[now, until]` window, then check whether that closest distance is
+    // within the collision radius.
+    pub fn bullets_to_collide(&self, until: Instant) -> Vec<Bullet> {
+        let now = Instant::now();
+        let horizon = until.saturating_duration_since(now).as_secs_f32();
+
+        let player = self.own_player();
+        let player_velocity = player.trajectory.last_velocity();
+
+        let mut threats: Vec<(Bullet, f32)> = self
+            .bullets
+            .iter()
+            .filter_map(|bullet| {
+                let relative_position = bullet.position.sub(&player.position);
+                let relative_velocity = bullet.velocity.sub(&player_velocity);
+
+                let speed_sq = relative_velocity.dot(&relative_velocity);
+                let t_star = if speed_sq > f32::EPSILON {
+                    -relative_position.dot(&relative_velocity) / speed_sq
+                } else {
+                    0.0
+                };
+                let impact_time = t_star.max(0.0).min(horizon.max(0.0));
+
+                let closest = relative_position + relative_velocity * impact_time;
+                if closest.length() <= self.collision_radius {
+                    Some((bullet.clone(), impact_time))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        threats.sort_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap());
+        threats.into_iter().map(|(bullet, _)| bullet).collect()
+    }
+}
+
+// Solves for the point where a bullet fired now at `bullet_speed` would meet
+// a target at `target_position` moving at constant `target_velocity`, i.e.
+// the smallest positive real root `t` of
+// `(u·u - speed²)t² + 2(q - s)·u·t + (q - s)·(q - s) = 0`.
+// Returns `None` if the target is outrunning the bullet.
+fn intercept_point(
+    shooter: &Point,
+    target_position: &Point,
+    target_velocity: &Vector,
+    bullet_speed: f32,
+) -> Option<Point> {
+    let relative = target_position.sub(shooter);
+    let a = target_velocity.dot(target_velocity) - bullet_speed * bullet_speed;
+    let b = 2.0 * relative.dot(target_velocity);
+    let c = relative.dot(&relative);
+
+    let t = if a.abs() < f32::EPSILON {
+        if b.abs() < f32::EPSILON {
+            None
+        } else {
+            let t = -c / b;
+            if t > 0.0 { Some(t) } else { None }
+        }
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            None
+        } else {
+            let sqrt_d = discriminant.sqrt();
+            [(-b + sqrt_d) / (2.0 * a), (-b - sqrt_d) / (2.0 * a)]
+                .into_iter()
+                .filter(|t| *t > 0.0)
+                .fold(None, |best, t| match best {
+                    Some(best) if best <= t => Some(best),
+                    _ => Some(t),
+                })
+        }
+    };
+
+    t.map(|t| target_position.project(target_velocity, Duration::from_secs_f32(t)))
+}
+
+// Ranks a hostile as an engagement target: projected score gain, discounted
+// by how far away they are.
+fn target_value(own_position: &Point, candidate: &Player, after: Duration) -> f32 {
+    let projected_score = candidate.score_history.project(after) as f32;
+    let distance = own_position.distance(&candidate.position);
+    projected_score - distance * TARGET_DISTANCE_PENALTY
 }
 
 pub struct Player {
@@ -118,14 +316,20 @@ impl Player {
     }
 }
 
+// How many samples to retain. At the server's tick rate this comfortably
+// covers `ScoreHistory::project`'s lookback window while keeping memory and
+// scan cost constant over an arbitrarily long match.
+const TRAJECTORY_CAPACITY: usize = 300;
+const SCORE_HISTORY_CAPACITY: usize = 300;
+
 pub struct Trajectory {
-    pub positions: Vec<(Point, Instant)>,
+    positions: RingBuffer<(Point, Instant)>,
 }
 
 impl Trajectory {
     pub fn new() -> Self {
         Self {
-            positions: Vec::new(),
+            positions: RingBuffer::new(TRAJECTORY_CAPACITY),
         }
     }
 
@@ -138,17 +342,22 @@ impl Trajectory {
     }
 
     pub fn last_velocity(&self) -> Vector {
-        let (last_position, last_time) = self.positions.last().unwrap();
-        if let Some((prev_position, prev_time)) = self.positions.get(self.positions.len() - 2) {
-            prev_position.velocity_to(last_position, *last_time - *prev_time)
-        } else {
-            // No idea, just return zeros.
-            Vector::zero()
+        if self.positions.len() < 2 {
+            // Only one sample so far, no idea, just return zeros.
+            return Vector::zero();
         }
+
+        let (last_position, last_time) = self.positions.last().unwrap();
+        let (prev_position, prev_time) = self.positions.get(self.positions.len() - 2).unwrap();
+        prev_position.velocity_to(last_position, *last_time - *prev_time)
     }
 
     // Some indication of the player's desire to move.
     pub fn ave_abs_velocity(&self) -> Vector {
+        if self.positions.len() < 2 {
+            return Vector::zero();
+        }
+
         let (items, sum) = self
             .positions
             .iter()
@@ -169,12 +378,14 @@ impl Trajectory {
 }
 
 pub struct ScoreHistory {
-    inner: Vec<(u32, Instant)>,
+    inner: RingBuffer<(u32, Instant)>,
 }
 
 impl ScoreHistory {
     pub fn new() -> Self {
-        Self { inner: Vec::new() }
+        Self {
+            inner: RingBuffer::new(SCORE_HISTORY_CAPACITY),
+        }
     }
 
     pub fn push(&mut self, score: u32, time: Instant) {
@@ -204,6 +415,7 @@ impl ScoreHistory {
     }
 }
 
+#[derive(Clone)]
 pub struct Bullet {
     pub position: Point,
     pub velocity: Vector,
@@ -218,4 +430,106 @@ impl Bullet {
             player_id: state.player_id,
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player_at(position: Point) -> Player {
+        let mut player = Player::new();
+        player.position = position;
+        player
+    }
+
+    #[test]
+    fn bullets_to_collide_flags_a_bullet_on_a_collision_course() {
+        let mut analyzer = Analyzer::new();
+        analyzer.set_collision_radius(5.0);
+        analyzer.players.insert(0, player_at(Point::zero()));
+        analyzer.set_own_player_id(0);
+        analyzer.bullets = vec![Bullet {
+            position: Point::new(0.0, -50.0),
+            velocity: Vector::new(0.0, 50.0),
+            player_id: 1,
+        }];
+
+        let hits = analyzer.bullets_to_collide(Instant::now() + Duration::from_secs(2));
+
+        assert_eq!(hits.len(), 1);
+    }
+
+    #[test]
+    fn bullets_to_collide_ignores_a_bullet_passing_outside_the_radius() {
+        let mut analyzer = Analyzer::new();
+        analyzer.set_collision_radius(5.0);
+        analyzer.players.insert(0, player_at(Point::zero()));
+        analyzer.set_own_player_id(0);
+        analyzer.bullets = vec![Bullet {
+            position: Point::new(100.0, -50.0),
+            velocity: Vector::new(0.0, 50.0),
+            player_id: 1,
+        }];
+
+        let hits = analyzer.bullets_to_collide(Instant::now() + Duration::from_secs(2));
+
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn bullets_to_collide_clamps_closest_approach_to_the_search_horizon() {
+        let mut analyzer = Analyzer::new();
+        analyzer.set_collision_radius(5.0);
+        analyzer.players.insert(0, player_at(Point::zero()));
+        analyzer.set_own_player_id(0);
+        // Would reach the player at t=5s, well past a 1s horizon; at t=1s
+        // it's still 40 units away, so it must not be reported as a threat.
+        analyzer.bullets = vec![Bullet {
+            position: Point::new(0.0, -50.0),
+            velocity: Vector::new(0.0, 10.0),
+            player_id: 1,
+        }];
+
+        let hits = analyzer.bullets_to_collide(Instant::now() + Duration::from_secs(1));
+
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn intercept_point_aims_directly_at_a_stationary_target() {
+        let shooter = Point::new(0.0, 0.0);
+        let target_position = Point::new(10.0, 0.0);
+        let target_velocity = Vector::zero();
+
+        let aim_point = intercept_point(&shooter, &target_position, &target_velocity, 5.0)
+            .expect("a stationary target is always reachable");
+
+        assert!((aim_point.distance(&target_position)) < 1e-3);
+    }
+
+    #[test]
+    fn intercept_point_gives_up_on_a_target_outrunning_the_bullet() {
+        let shooter = Point::new(0.0, 0.0);
+        let target_position = Point::new(10.0, 0.0);
+        // Fleeing straight away faster than the bullet can ever catch up.
+        let target_velocity = Vector::new(20.0, 0.0);
+
+        let aim_point = intercept_point(&shooter, &target_position, &target_velocity, 5.0);
+
+        assert!(aim_point.is_none());
+    }
+
+    #[test]
+    fn intercept_point_leads_a_head_on_closing_target() {
+        let shooter = Point::new(0.0, 0.0);
+        let target_position = Point::new(10.0, 0.0);
+        // Closing straight in on the shooter.
+        let target_velocity = Vector::new(-5.0, 0.0);
+
+        let aim_point = intercept_point(&shooter, &target_position, &target_velocity, 10.0)
+            .expect("a closing target is reachable");
+
+        // t = 2/3s, so the target (and the aim point) is at x = 10 - 5*2/3.
+        assert!((aim_point.distance(&Point::new(20.0 / 3.0, 0.0))) < 1e-3);
+    }
 }
\ No newline at end of file
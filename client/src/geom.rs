@@ -0,0 +1,120 @@
+use std::ops::{Add, Div, Mul};
+use std::time::Duration;
+
+/// An angle in radians, kept as a distinct type so callers can't
+/// accidentally mix it up with a raw scalar (e.g. a distance or a speed).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Radian(f32);
+
+impl Radian {
+    pub fn new(value: f32) -> Self {
+        Self(value)
+    }
+
+    pub fn zero() -> Self {
+        Self(0.0)
+    }
+
+    pub fn value(&self) -> f32 {
+        self.0
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vector {
+    pub dx: f32,
+    pub dy: f32,
+}
+
+impl Vector {
+    pub fn new(dx: f32, dy: f32) -> Self {
+        Self { dx, dy }
+    }
+
+    pub fn zero() -> Self {
+        Self::new(0.0, 0.0)
+    }
+
+    pub fn with_angle(angle: f32) -> Self {
+        Self::new(angle.cos(), angle.sin())
+    }
+
+    pub fn abs(&self) -> Self {
+        Self::new(self.dx.abs(), self.dy.abs())
+    }
+
+    pub fn dot(&self, other: &Self) -> f32 {
+        self.dx * other.dx + self.dy * other.dy
+    }
+
+    pub fn length(&self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        Self::new(self.dx - other.dx, self.dy - other.dy)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.dx + other.dx, self.dy + other.dy)
+    }
+}
+
+impl Mul<f32> for Vector {
+    type Output = Vector;
+
+    fn mul(self, scalar: f32) -> Vector {
+        Vector::new(self.dx * scalar, self.dy * scalar)
+    }
+}
+
+impl Div<f32> for Vector {
+    type Output = Vector;
+
+    fn div(self, scalar: f32) -> Vector {
+        Vector::new(self.dx / scalar, self.dy / scalar)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Point {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn zero() -> Self {
+        Self::new(0.0, 0.0)
+    }
+
+    pub fn distance(&self, other: &Point) -> f32 {
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt()
+    }
+
+    pub fn angle_to(&self, other: &Point) -> Radian {
+        Radian::new((other.y - self.y).atan2(other.x - self.x))
+    }
+
+    pub fn velocity_to(&self, dest: &Point, time: Duration) -> Vector {
+        let secs = time.as_secs_f32();
+        Vector::new((dest.x - self.x) / secs, (dest.y - self.y) / secs)
+    }
+
+    pub fn project(&self, vel: &Vector, time: Duration) -> Point {
+        let secs = time.as_secs_f32();
+        Point::new(self.x + vel.dx * secs, self.y + vel.dy * secs)
+    }
+
+    /// The vector pointing from `other` to `self`.
+    pub fn sub(&self, other: &Point) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
@@ -0,0 +1,214 @@
+use crate::analyzer::{Analyzer, Bullet, Player};
+use crate::geom::*;
+use crate::timing::{Rng, TimeKeeper};
+use std::time::{Duration, Instant};
+
+const HORIZON_STEPS: usize = 6;
+const STEP_DURATION: Duration = Duration::from_millis(150);
+const POPULATION_SIZE: usize = 24;
+const ELITE_COUNT: usize = 4;
+const TOURNAMENT_SIZE: usize = 4;
+const MUTATION_RATE: f32 = 0.1;
+const MUTATION_STD: f32 = 0.3;
+const THRUST_SPEED: f32 = 80.0; // world units/sec at full thrust
+const COLLISION_PENALTY: f32 = 1000.0;
+const CLOSING_REWARD: f32 = 2.0; // per unit of distance closed on the engagement target
+
+// A single tick's worth of stick input: a heading and how hard to push it.
+#[derive(Clone, Copy, Debug)]
+pub struct Command {
+    pub angle: f32,
+    pub thrust: f32,
+}
+
+// A candidate plan: `HORIZON_STEPS` commands applied one after another.
+#[derive(Clone, Debug)]
+struct Individual {
+    genes: Vec<Command>,
+    fitness: f32,
+}
+
+impl Individual {
+    fn random(len: usize, rng: &mut Rng) -> Self {
+        let genes = (0..len).map(|_| random_command(rng)).collect();
+        Self { genes, fitness: f32::MIN }
+    }
+}
+
+// Evolves a short sequence of movement commands and returns the first one
+// to apply this tick, re-planning from scratch every call. The RNG stream
+// is seeded once and carried across calls, so successive ticks keep
+// advancing through it instead of replaying the same draws.
+pub struct Planner {
+    rng: Rng,
+}
+
+impl Planner {
+    pub fn new(seed: u64) -> Self {
+        Self { rng: Rng::new(seed) }
+    }
+
+    // Evolves candidate plans until `budget` runs out (checked between
+    // generations, so the last generation in flight always finishes) and
+    // returns the first command of the fittest individual found so far.
+    pub fn plan(&mut self, analyzer: &Analyzer, budget: Duration) -> Command {
+        let clock = TimeKeeper::new();
+
+        // The threat list doesn't depend on the individual being scored, so
+        // it's computed once per `plan()` call rather than once per
+        // evaluation - that budget goes toward more generations instead of
+        // repeating identical work.
+        let horizon = STEP_DURATION * HORIZON_STEPS as u32;
+        let threats = analyzer.bullets_to_collide(Instant::now() + horizon);
+        let target = analyzer.most_valuable_hostile(horizon);
+
+        let mut population: Vec<Individual> = (0..POPULATION_SIZE)
+            .map(|_| Individual::random(HORIZON_STEPS, &mut self.rng))
+            .collect();
+        for individual in population.iter_mut() {
+            individual.fitness = self.evaluate(analyzer, individual, &threats, target);
+        }
+
+        loop {
+            population.sort_by(|a, b| b.fitness.partial_cmp(&a.fitness).unwrap());
+
+            let mut next_gen: Vec<Individual> = population[..ELITE_COUNT].to_vec();
+            while next_gen.len() < POPULATION_SIZE {
+                let parent_a = tournament_select(&population, &mut self.rng);
+                let parent_b = tournament_select(&population, &mut self.rng);
+                let mut child = crossover(parent_a, parent_b, &mut self.rng);
+                mutate(&mut child, &mut self.rng);
+                child.fitness = self.evaluate(analyzer, &child, &threats, target);
+                next_gen.push(child);
+            }
+            population = next_gen;
+
+            if clock.is_over(budget) {
+                break;
+            }
+        }
+
+        population.sort_by(|a, b| b.fitness.partial_cmp(&a.fitness).unwrap());
+        population[0].genes[0]
+    }
+
+    // Rolls the gene vector forward from the player's current state and
+    // scores the result: reward for closing the distance on `target` (a
+    // proxy for projected score gain - engaging a valuable hostile is how
+    // score is made), penalized for flying through a bullet's impact window.
+    fn evaluate(
+        &self,
+        analyzer: &Analyzer,
+        individual: &Individual,
+        threats: &[Bullet],
+        target: Option<&Player>,
+    ) -> f32 {
+        let player = analyzer.own_player();
+
+        let mut position = player.position;
+        let mut penalty = 0.0;
+        let mut elapsed = Duration::from_secs(0);
+
+        for command in &individual.genes {
+            let velocity = Vector::with_angle(command.angle) * (command.thrust * THRUST_SPEED);
+            position = position.project(&velocity, STEP_DURATION);
+            elapsed += STEP_DURATION;
+
+            for bullet in threats {
+                let bullet_position = bullet.position.project(&bullet.velocity, elapsed);
+                if bullet_position.distance(&position) <= analyzer.collision_radius() {
+                    penalty += COLLISION_PENALTY;
+                }
+            }
+        }
+
+        let score_gain = match target {
+            Some(target) => {
+                let starting_distance = player.position.distance(&target.position);
+                let ending_distance = position.distance(&target.position);
+                (starting_distance - ending_distance) * CLOSING_REWARD
+            }
+            None => 0.0,
+        };
+
+        score_gain - penalty
+    }
+}
+
+fn tournament_select<'a>(population: &'a [Individual], rng: &mut Rng) -> &'a Individual {
+    (0..TOURNAMENT_SIZE)
+        .map(|_| &population[rng.index(population.len())])
+        .max_by(|a, b| a.fitness.partial_cmp(&b.fitness).unwrap())
+        .unwrap()
+}
+
+fn crossover(parent_a: &Individual, parent_b: &Individual, rng: &mut Rng) -> Individual {
+    let point = rng.index(parent_a.genes.len());
+    let genes = parent_a.genes[..point]
+        .iter()
+        .chain(parent_b.genes[point..].iter())
+        .cloned()
+        .collect();
+    Individual {
+        genes,
+        fitness: f32::MIN,
+    }
+}
+
+fn mutate(individual: &mut Individual, rng: &mut Rng) {
+    for gene in individual.genes.iter_mut() {
+        if rng.next_f32() < MUTATION_RATE {
+            gene.angle += rng.gaussian() * MUTATION_STD;
+        }
+        if rng.next_f32() < MUTATION_RATE {
+            gene.thrust = (gene.thrust + rng.gaussian() * MUTATION_STD).clamp(0.0, 1.0);
+        }
+    }
+}
+
+fn random_command(rng: &mut Rng) -> Command {
+    Command {
+        angle: rng.next_f32() * std::f32::consts::TAU,
+        thrust: rng.next_f32(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn individual_with_fitness(fitness: f32) -> Individual {
+        Individual {
+            genes: Vec::new(),
+            fitness,
+        }
+    }
+
+    #[test]
+    fn tournament_select_picks_the_fittest_individual_drawn_for_a_seed() {
+        let population: Vec<Individual> = (0..6).map(|i| individual_with_fitness(i as f32)).collect();
+        let mut rng = Rng::new(42);
+
+        let winner = tournament_select(&population, &mut rng);
+
+        assert_eq!(winner.fitness, 5.0);
+    }
+
+    #[test]
+    fn crossover_splits_genes_at_the_point_drawn_for_a_seed() {
+        let parent_a = Individual {
+            genes: vec![Command { angle: 1.0, thrust: 0.0 }; 6],
+            fitness: 0.0,
+        };
+        let parent_b = Individual {
+            genes: vec![Command { angle: 2.0, thrust: 0.0 }; 6],
+            fitness: 0.0,
+        };
+        let mut rng = Rng::new(42);
+
+        let child = crossover(&parent_a, &parent_b, &mut rng);
+
+        let angles: Vec<f32> = child.genes.iter().map(|gene| gene.angle).collect();
+        assert_eq!(angles, vec![1.0, 1.0, 1.0, 1.0, 2.0, 2.0]);
+    }
+}
@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+// How a player should be treated for targeting purposes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relationship {
+    Hostile,
+    Neutral,
+    SelfPlayer,
+}
+
+// Per-player relationship overrides, keyed by player id, falling back to a
+// default for anyone not explicitly classified (loaded from a small
+// team/relationship config, e.g. "everyone's hostile" for free-for-all or
+// "everyone on my team is neutral" for team modes).
+pub struct Relationships {
+    default: Relationship,
+    overrides: HashMap<u32, Relationship>,
+}
+
+impl Relationships {
+    pub fn new(default: Relationship) -> Self {
+        Self {
+            default,
+            overrides: HashMap::new(),
+        }
+    }
+
+    pub fn set(&mut self, player_id: u32, relationship: Relationship) {
+        self.overrides.insert(player_id, relationship);
+    }
+
+    pub fn of(&self, player_id: u32) -> Relationship {
+        self.overrides.get(&player_id).copied().unwrap_or(self.default)
+    }
+
+    pub fn is_hostile(&self, player_id: u32) -> bool {
+        self.of(player_id) == Relationship::Hostile
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_hostile_falls_back_to_the_default_for_unlisted_players() {
+        let relationships = Relationships::new(Relationship::Hostile);
+
+        assert!(relationships.is_hostile(1));
+    }
+
+    #[test]
+    fn is_hostile_reflects_a_per_player_override() {
+        let mut relationships = Relationships::new(Relationship::Hostile);
+        relationships.set(1, Relationship::SelfPlayer);
+        relationships.set(2, Relationship::Neutral);
+
+        assert!(!relationships.is_hostile(1));
+        assert!(!relationships.is_hostile(2));
+        assert!(relationships.is_hostile(3));
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::VecDeque;
+
+// A fixed-capacity FIFO: pushing past capacity drops the oldest item. Used
+// to bound analytics windows (trajectory samples, score samples, ...) so
+// per-tick cost stays constant no matter how long the match runs.
+pub struct RingBuffer<T> {
+    capacity: usize,
+    items: VecDeque<T>,
+}
+
+impl<T> RingBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            items: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, item: T) {
+        if self.capacity == 0 {
+            return;
+        }
+        while self.items.len() >= self.capacity {
+            self.items.pop_front();
+        }
+        self.items.push_back(item);
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.items.back()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.items.get(index)
+    }
+
+    pub fn iter(&self) -> std::collections::vec_deque::Iter<T> {
+        self.items.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_evicts_oldest_once_capacity_is_reached() {
+        let mut buffer = RingBuffer::new(3);
+        buffer.push(1);
+        buffer.push(2);
+        buffer.push(3);
+        buffer.push(4);
+
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn push_on_a_zero_capacity_buffer_stays_empty_and_returns() {
+        let mut buffer = RingBuffer::new(0);
+        buffer.push(1);
+        buffer.push(2);
+
+        assert_eq!(buffer.len(), 0);
+        assert_eq!(buffer.last(), None);
+    }
+}
@@ -0,0 +1,99 @@
+use std::time::{Duration, Instant};
+
+// Tracks how long the current decision tick has been running so search
+// routines (the GA planner, collision search, target scan, ...) can keep
+// iterating while there's time left in the tick and bail out with their
+// best-so-far result once the budget is spent.
+pub struct TimeKeeper {
+    start: Instant,
+}
+
+impl TimeKeeper {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+
+    pub fn is_over(&self, threshold: Duration) -> bool {
+        self.start.elapsed() >= threshold
+    }
+}
+
+// A small, fast, deterministic PRNG (xorshift64, seeded via splitmix64) used
+// wherever we need reproducible randomness without pulling in a `rand`
+// dependency.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(splitmix64(seed))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    // Approximate standard normal via the sum of twelve uniforms
+    // (Irwin-Hall), plenty good enough for mutation noise.
+    pub fn gaussian(&mut self) -> f32 {
+        (0..12).map(|_| self.next_f32()).sum::<f32>() - 6.0
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        (self.next_u64() % len as u64) as usize
+    }
+}
+
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    let z = z ^ (z >> 31);
+    // xorshift's state must never be zero.
+    if z == 0 {
+        1
+    } else {
+        z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_reproduces_the_same_draw_sequence() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+
+        let draws_a: Vec<u64> = (0..8).map(|_| a.next_u64()).collect();
+        let draws_b: Vec<u64> = (0..8).map(|_| b.next_u64()).collect();
+
+        assert_eq!(draws_a, draws_b);
+    }
+
+    #[test]
+    fn index_stays_within_bounds_and_is_seed_deterministic() {
+        let mut rng = Rng::new(42);
+        let indices: Vec<usize> = (0..12).map(|_| rng.index(6)).collect();
+
+        assert!(indices.iter().all(|&i| i < 6));
+        assert_eq!(indices, vec![4, 2, 0, 5, 2, 0, 1, 4, 4, 4, 0, 4]);
+    }
+
+    #[test]
+    fn is_over_is_false_until_the_threshold_elapses() {
+        let clock = TimeKeeper::new();
+        assert!(!clock.is_over(Duration::from_secs(60)));
+    }
+}